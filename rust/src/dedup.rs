@@ -0,0 +1,212 @@
+//! Content-defined chunking (FastCDC) and a refcounted chunk store, used to
+//! deduplicate storage for large, near-identical values.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use twox_hash::xxh3;
+
+/// Chunk smaller than this are never cut early.
+const MIN_SIZE: usize = 2 * 1024;
+/// Target average chunk size; the boundary mask loosens past this point.
+const AVG_SIZE: usize = 8 * 1024;
+/// Chunks are force-cut if they reach this size without a natural boundary.
+const MAX_SIZE: usize = 64 * 1024;
+
+/// Stricter mask (more one-bits) applied below `AVG_SIZE`, making boundaries
+/// rarer so chunks lean larger than the average.
+const MASK_S: u64 = 0x0003_5907_0353_0000;
+/// Looser mask (fewer one-bits) applied beyond `AVG_SIZE`, making boundaries
+/// more likely so oversized chunks get cut sooner.
+const MASK_L: u64 = 0x0000_d900_0353_0000;
+
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 256-entry table of fixed pseudo-random constants used to roll the
+/// FastCDC gear fingerprint.
+const fn build_gear() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0usize;
+    let mut seed = 0x2545_F491_4F6C_DD1D_u64;
+    while i < 256 {
+        seed = splitmix64(seed);
+        table[i] = seed;
+        i += 1;
+    }
+    table
+}
+
+static GEAR: [u64; 256] = build_gear();
+
+/// Splits `data` into content-defined chunks using normalized FastCDC.
+fn cdc_chunks(data: &[u8]) -> Vec<&[u8]> {
+    let len = data.len();
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+
+    while start < len {
+        let remaining = len - start;
+        if remaining <= MIN_SIZE {
+            chunks.push(&data[start..len]);
+            break;
+        }
+
+        let max_len = remaining.min(MAX_SIZE);
+        let mut fp: u64 = 0;
+        let mut cut = max_len;
+        let mut pos = MIN_SIZE;
+
+        while pos < max_len {
+            let byte = data[start + pos];
+            fp = (fp << 1).wrapping_add(GEAR[byte as usize]);
+
+            let mask = if pos < AVG_SIZE { MASK_S } else { MASK_L };
+            if fp & mask == 0 {
+                cut = pos;
+                break;
+            }
+            pos += 1;
+        }
+
+        chunks.push(&data[start..start + cut]);
+        start += cut;
+    }
+
+    chunks
+}
+
+/// A single deduplicated chunk, shared by every `CacheEntry` that references
+/// it.
+struct ChunkEntry {
+    data: Arc<Vec<u8>>,
+    refcount: usize,
+}
+
+/// Global content store for deduplicated chunks, keyed by xxh3 hash.
+static CONTENT_STORE: Mutex<Option<HashMap<u64, ChunkEntry>>> = Mutex::new(None);
+
+fn with_store<R>(f: impl FnOnce(&mut HashMap<u64, ChunkEntry>) -> R) -> R {
+    let mut guard = CONTENT_STORE.lock().unwrap();
+    let map = guard.get_or_insert_with(HashMap::new);
+    f(map)
+}
+
+/// Chunks `value`, storing each unique chunk (or bumping its refcount if
+/// already present), and returns the ordered list of chunk hashes.
+pub(crate) fn store_value(value: &[u8]) -> Vec<u64> {
+    with_store(|map| {
+        cdc_chunks(value)
+            .into_iter()
+            .map(|chunk| {
+                let hash = xxh3::hash64(chunk);
+                map.entry(hash)
+                    .and_modify(|entry| entry.refcount += 1)
+                    .or_insert_with(|| ChunkEntry {
+                        data: Arc::new(chunk.to_vec()),
+                        refcount: 1,
+                    });
+                hash
+            })
+            .collect()
+    })
+}
+
+/// Reassembles the original value from its ordered chunk hashes. Returns
+/// `None` if any chunk is missing from the store.
+pub(crate) fn reassemble(hashes: &[u64]) -> Option<Vec<u8>> {
+    with_store(|map| {
+        let mut result = Vec::new();
+        for hash in hashes {
+            result.extend_from_slice(&map.get(hash)?.data);
+        }
+        Some(result)
+    })
+}
+
+/// Decrements the refcount of each chunk in `hashes`, freeing any chunk that
+/// drops to zero references.
+pub(crate) fn release(hashes: &[u64]) {
+    with_store(|map| {
+        for hash in hashes {
+            let mut drop_chunk = false;
+            if let Some(entry) = map.get_mut(hash) {
+                entry.refcount = entry.refcount.saturating_sub(1);
+                drop_chunk = entry.refcount == 0;
+            }
+            if drop_chunk {
+                map.remove(hash);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministically generates `len` bytes of non-repeating pseudo-random
+    /// data from `seed`, using the module's own `splitmix64` as the PRNG.
+    /// A constant-byte fixture such as `"x".repeat(n)` never trips the Gear
+    /// fingerprint's boundary check, so it is only ever force-cut at
+    /// `MAX_SIZE`; this is needed to actually exercise content-defined
+    /// splitting.
+    fn pseudo_random_bytes(len: usize, seed: u64) -> Vec<u8> {
+        let mut out = Vec::with_capacity(len);
+        let mut state = seed;
+        while out.len() < len {
+            state = splitmix64(state);
+            out.extend_from_slice(&state.to_le_bytes());
+        }
+        out.truncate(len);
+        out
+    }
+
+    #[test]
+    fn cdc_chunks_splits_large_varied_data_into_multiple_chunks() {
+        let data = pseudo_random_bytes(200 * 1024, 1);
+        let chunks = cdc_chunks(&data);
+
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), data.len());
+        assert_eq!(chunks.concat(), data);
+        for chunk in &chunks {
+            assert!(chunk.len() <= MAX_SIZE);
+        }
+    }
+
+    #[test]
+    fn store_value_and_reassemble_roundtrip_multi_chunk_data() {
+        let data = pseudo_random_bytes(200 * 1024, 2);
+
+        let hashes = store_value(&data);
+        assert!(hashes.len() > 1);
+
+        let reassembled = reassemble(&hashes).unwrap();
+        assert_eq!(reassembled, data);
+
+        release(&hashes);
+    }
+
+    #[test]
+    fn store_value_shares_chunks_and_refcounts_across_owners() {
+        let data = pseudo_random_bytes(150 * 1024, 3);
+
+        let first = store_value(&data);
+        let second = store_value(&data);
+        assert_eq!(first, second);
+
+        // Releasing one owner's reference must not break the other owner's
+        // ability to reassemble the shared chunks.
+        release(&first);
+        assert_eq!(reassemble(&second).unwrap(), data);
+
+        // Only once both owners release does the content actually go away.
+        release(&second);
+        assert!(reassemble(&second).is_none());
+    }
+}
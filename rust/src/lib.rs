@@ -6,32 +6,148 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use redis::{Commands, Connection, RedisResult};
 use serde::{Deserialize, Serialize};
+use twox_hash::xxh3;
+
+mod dedup;
+mod redis_backend;
+
+/// Compression codec applied to a stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Codec {
+    None,
+    Lz4,
+    Zstd { level: i32 },
+}
+
+impl Codec {
+    fn compress(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Codec::None => Some(data.to_vec()),
+            Codec::Lz4 => Some(lz4_flex::compress_prepend_size(data)),
+            Codec::Zstd { level } => zstd::stream::encode_all(data, level).ok(),
+        }
+    }
+
+    fn decompress(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Codec::None => Some(data.to_vec()),
+            Codec::Lz4 => lz4_flex::decompress_size_prepended(data).ok(),
+            Codec::Zstd { .. } => zstd::stream::decode_all(data).ok(),
+        }
+    }
+}
+
+/// Decodes the `highper_cache_memory_set_compressed` FFI codec id (`0` =
+/// none, `1` = LZ4, `2` = Zstd) into a `Codec`, applying `zstd_level` when
+/// Zstd is selected.
+fn codec_from_ffi(codec: i32, zstd_level: i32) -> Codec {
+    match codec {
+        1 => Codec::Lz4,
+        2 => Codec::Zstd { level: zstd_level },
+        _ => Codec::None,
+    }
+}
+
+/// The storage representation of a cache entry's value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum CacheValue {
+    /// The value as stored directly.
+    Raw(String),
+    /// The value as an ordered list of content-defined chunk hashes, stored
+    /// deduplicated in the `dedup` content store.
+    Chunked(Vec<u64>),
+    /// The value compressed at rest with `codec`; stored as raw bytes since
+    /// base64 is only needed when crossing the C string boundary.
+    Compressed { codec: Codec, bytes: Vec<u8> },
+}
 
 /// Cache entry with TTL support
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct CacheEntry {
-    value: String,
+pub(crate) struct CacheEntry {
+    value: CacheValue,
+    /// xxh3 checksum of the logical (pre-chunking, pre-compression) value
+    /// bytes, verified on every read to detect corruption.
+    checksum: u64,
     expires_at: Option<u64>,
     created_at: u64,
 }
 
 impl CacheEntry {
-    fn new(value: String, ttl: u64) -> Self {
+    pub(crate) fn new(value: String, ttl: u64) -> Self {
+        let checksum = xxh3::hash64(value.as_bytes());
+        Self::with_value(CacheValue::Raw(value), checksum, ttl)
+    }
+
+    fn new_chunked(chunk_hashes: Vec<u64>, raw_value: &[u8], ttl: u64) -> Self {
+        let checksum = xxh3::hash64(raw_value);
+        Self::with_value(CacheValue::Chunked(chunk_hashes), checksum, ttl)
+    }
+
+    fn new_compressed(codec: Codec, compressed_bytes: Vec<u8>, raw_value: &[u8], ttl: u64) -> Self {
+        let checksum = xxh3::hash64(raw_value);
+        Self::with_value(
+            CacheValue::Compressed {
+                codec,
+                bytes: compressed_bytes,
+            },
+            checksum,
+            ttl,
+        )
+    }
+
+    fn with_value(value: CacheValue, checksum: u64, ttl: u64) -> Self {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        
+
         let expires_at = if ttl > 0 { Some(now + ttl) } else { None };
-        
+
         Self {
             value,
+            checksum,
             expires_at,
             created_at: now,
         }
     }
-    
-    fn is_expired(&self) -> bool {
+
+    /// Materializes the stored value as a string, reassembling deduplicated
+    /// chunks or decompressing when necessary. Returns `None` if a chunked
+    /// entry has a chunk missing from the content store, a compressed entry
+    /// fails to decompress, or the resulting bytes aren't valid UTF-8.
+    pub(crate) fn materialize(&self) -> Option<String> {
+        match &self.value {
+            CacheValue::Raw(value) => Some(value.clone()),
+            CacheValue::Chunked(hashes) => {
+                dedup::reassemble(hashes).and_then(|bytes| String::from_utf8(bytes).ok())
+            }
+            CacheValue::Compressed { codec, bytes } => {
+                codec.decompress(bytes).and_then(|bytes| String::from_utf8(bytes).ok())
+            }
+        }
+    }
+
+    /// Materializes the value and verifies it against the stored checksum.
+    /// Returns `None` if the entry is missing data or the checksum doesn't
+    /// match (possible corruption).
+    pub(crate) fn verified_value(&self) -> Option<String> {
+        let value = self.materialize()?;
+        if xxh3::hash64(value.as_bytes()) == self.checksum {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Releases any deduplicated chunks this entry references. Must be
+    /// called whenever an entry is removed from the cache.
+    pub(crate) fn release_chunks(&self) {
+        if let CacheValue::Chunked(hashes) = &self.value {
+            dedup::release(hashes);
+        }
+    }
+
+    pub(crate) fn is_expired(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
             let now = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -44,15 +160,98 @@ impl CacheEntry {
     }
 }
 
+/// Number of shards used by the memory cache when no explicit configuration
+/// has been applied yet.
+const DEFAULT_SHARD_COUNT: usize = 64;
+
+/// A single lock-striped shard of the memory cache.
+pub(crate) type Shard = Mutex<HashMap<String, CacheEntry>>;
+
+/// Sharded memory cache storage.
+///
+/// Keys are routed to a shard by hashing with xxh3, so concurrent callers
+/// touching different keys contend on different locks instead of a single
+/// global mutex.
+pub(crate) struct ShardedCache {
+    shards: Vec<Shard>,
+    max_entries_per_shard: usize,
+}
+
+impl ShardedCache {
+    fn new(shard_count: usize, max_entries_per_shard: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        let mut shards = Vec::with_capacity(shard_count);
+        for _ in 0..shard_count {
+            shards.push(Mutex::new(HashMap::new()));
+        }
+        Self {
+            shards,
+            max_entries_per_shard,
+        }
+    }
+
+    pub(crate) fn shard_for(&self, key: &str) -> &Shard {
+        let hash = xxh3::hash64(key.as_bytes());
+        let index = (hash as usize) % self.shards.len();
+        &self.shards[index]
+    }
+
+    /// Makes room in `map` for an insert of `key` when the shard is at
+    /// capacity, evicting the oldest entry first. A no-op when `key` is
+    /// already present, since overwriting it is not net growth.
+    pub(crate) fn evict_if_full(&self, map: &mut HashMap<String, CacheEntry>, key: &str) {
+        if self.max_entries_per_shard == 0
+            || map.len() < self.max_entries_per_shard
+            || map.contains_key(key)
+        {
+            return;
+        }
+        if let Some(oldest_key) = map
+            .iter()
+            .min_by_key(|(_, entry)| entry.created_at)
+            .map(|(key, _)| key.clone())
+        {
+            if let Some(entry) = map.remove(&oldest_key) {
+                entry.release_chunks();
+            }
+        }
+    }
+}
+
 /// Global cache storage for memory operations
-static MEMORY_CACHE: Mutex<Option<HashMap<String, CacheEntry>>> = Mutex::new(None);
+static MEMORY_CACHE: Mutex<Option<Arc<ShardedCache>>> = Mutex::new(None);
 
-/// Initialize memory cache
-fn init_memory_cache() {
+/// Initialize memory cache (using the default shard configuration), and
+/// return a handle to it.
+pub(crate) fn get_cache() -> Arc<ShardedCache> {
     let mut cache = MEMORY_CACHE.lock().unwrap();
     if cache.is_none() {
-        *cache = Some(HashMap::new());
+        *cache = Some(Arc::new(ShardedCache::new(DEFAULT_SHARD_COUNT, 0)));
     }
+    cache.as_ref().unwrap().clone()
+}
+
+/// Reconfigure the memory cache with a new shard count and per-shard entry
+/// cap, discarding any existing entries. `max_entries_per_shard == 0` means
+/// unlimited.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_configure(
+    shard_count: usize,
+    max_entries_per_shard: usize,
+) -> bool {
+    if shard_count == 0 {
+        return false;
+    }
+    let mut cache = MEMORY_CACHE.lock().unwrap();
+    if let Some(outgoing) = cache.as_ref() {
+        for shard in &outgoing.shards {
+            for (_, entry) in shard.lock().unwrap().drain() {
+                entry.release_chunks();
+            }
+        }
+    }
+    *cache = Some(Arc::new(ShardedCache::new(shard_count, max_entries_per_shard)));
+    true
 }
 
 /// Convert C string to Rust string
@@ -98,14 +297,16 @@ pub extern "C" fn highper_cache_memory_set(
 ) -> bool {
     unsafe {
         if let (Ok(key_str), Ok(value_str)) = (c_str_to_string(key), c_str_to_string(value)) {
-            init_memory_cache();
-            
-            let mut cache = MEMORY_CACHE.lock().unwrap();
-            if let Some(ref mut map) = *cache {
-                let entry = CacheEntry::new(value_str, ttl);
-                map.insert(key_str, entry);
-                return true;
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let mut map = shard.lock().unwrap();
+            cache.evict_if_full(&mut map, &key_str);
+            let entry = CacheEntry::new(value_str, ttl);
+            if let Some(previous) = map.insert(key_str, entry) {
+                previous.release_chunks();
             }
+            return true;
         }
     }
     false
@@ -115,16 +316,25 @@ pub extern "C" fn highper_cache_memory_set(
 pub extern "C" fn highper_cache_memory_get(key: *const c_char) -> *mut c_char {
     unsafe {
         if let Ok(key_str) = c_str_to_string(key) {
-            init_memory_cache();
-            
-            let mut cache = MEMORY_CACHE.lock().unwrap();
-            if let Some(ref mut map) = *cache {
-                if let Some(entry) = map.get(&key_str) {
-                    if entry.is_expired() {
-                        map.remove(&key_str);
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let mut map = shard.lock().unwrap();
+            if let Some(entry) = map.get(&key_str) {
+                if entry.is_expired() {
+                    if let Some(entry) = map.remove(&key_str) {
+                        entry.release_chunks();
+                    }
+                    return std::ptr::null_mut();
+                }
+                match entry.verified_value() {
+                    Some(value) => return string_to_c_str(value),
+                    None => {
+                        if let Some(entry) = map.remove(&key_str) {
+                            entry.release_chunks();
+                        }
                         return std::ptr::null_mut();
                     }
-                    return string_to_c_str(entry.value.clone());
                 }
             }
         }
@@ -136,11 +346,13 @@ pub extern "C" fn highper_cache_memory_get(key: *const c_char) -> *mut c_char {
 pub extern "C" fn highper_cache_memory_delete(key: *const c_char) -> bool {
     unsafe {
         if let Ok(key_str) = c_str_to_string(key) {
-            init_memory_cache();
-            
-            let mut cache = MEMORY_CACHE.lock().unwrap();
-            if let Some(ref mut map) = *cache {
-                return map.remove(&key_str).is_some();
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let mut map = shard.lock().unwrap();
+            if let Some(entry) = map.remove(&key_str) {
+                entry.release_chunks();
+                return true;
             }
         }
     }
@@ -149,31 +361,32 @@ pub extern "C" fn highper_cache_memory_delete(key: *const c_char) -> bool {
 
 #[no_mangle]
 pub extern "C" fn highper_cache_memory_clear() -> bool {
-    init_memory_cache();
-    
-    let mut cache = MEMORY_CACHE.lock().unwrap();
-    if let Some(ref mut map) = *cache {
-        map.clear();
-        return true;
+    let cache = get_cache();
+    for shard in &cache.shards {
+        let mut map = shard.lock().unwrap();
+        for (_, entry) in map.drain() {
+            entry.release_chunks();
+        }
     }
-    false
+    true
 }
 
 #[no_mangle]
 pub extern "C" fn highper_cache_memory_exists(key: *const c_char) -> bool {
     unsafe {
         if let Ok(key_str) = c_str_to_string(key) {
-            init_memory_cache();
-            
-            let mut cache = MEMORY_CACHE.lock().unwrap();
-            if let Some(ref mut map) = *cache {
-                if let Some(entry) = map.get(&key_str) {
-                    if entry.is_expired() {
-                        map.remove(&key_str);
-                        return false;
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let mut map = shard.lock().unwrap();
+            if let Some(entry) = map.get(&key_str) {
+                if entry.is_expired() {
+                    if let Some(entry) = map.remove(&key_str) {
+                        entry.release_chunks();
                     }
-                    return true;
+                    return false;
                 }
+                return true;
             }
         }
     }
@@ -182,28 +395,91 @@ pub extern "C" fn highper_cache_memory_exists(key: *const c_char) -> bool {
 
 #[no_mangle]
 pub extern "C" fn highper_cache_memory_cleanup() -> u64 {
-    init_memory_cache();
-    
-    let mut cache = MEMORY_CACHE.lock().unwrap();
-    if let Some(ref mut map) = *cache {
-        let initial_count = map.len();
-        map.retain(|_, entry| !entry.is_expired());
-        return (initial_count - map.len()) as u64;
+    let cache = get_cache();
+    let mut removed = 0u64;
+    for shard in &cache.shards {
+        let mut map = shard.lock().unwrap();
+        let expired_keys: Vec<String> = map
+            .iter()
+            .filter(|(_, entry)| entry.is_expired())
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired_keys {
+            if let Some(entry) = map.remove(&key) {
+                entry.release_chunks();
+                removed += 1;
+            }
+        }
     }
-    0
+    removed
 }
 
 #[no_mangle]
 pub extern "C" fn highper_cache_memory_count() -> u64 {
-    init_memory_cache();
-    
-    let cache = MEMORY_CACHE.lock().unwrap();
-    if let Some(ref map) = *cache {
-        return map.len() as u64;
+    let cache = get_cache();
+    cache
+        .shards
+        .iter()
+        .map(|shard| shard.lock().unwrap().len() as u64)
+        .sum()
+}
+
+/// Checks a single entry's checksum without removing it on success.
+/// Returns `1` if present and valid, `-1` if present but corrupt (the entry
+/// is purged as a side effect), or `0` if absent.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_verify(key: *const c_char) -> i32 {
+    unsafe {
+        if let Ok(key_str) = c_str_to_string(key) {
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let mut map = shard.lock().unwrap();
+            if let Some(entry) = map.get(&key_str) {
+                if entry.is_expired() {
+                    if let Some(entry) = map.remove(&key_str) {
+                        entry.release_chunks();
+                    }
+                    return 0;
+                }
+                if entry.verified_value().is_some() {
+                    return 1;
+                }
+                if let Some(entry) = map.remove(&key_str) {
+                    entry.release_chunks();
+                }
+                return -1;
+            }
+        }
     }
     0
 }
 
+/// Sweeps every shard, purging any entry whose checksum doesn't match its
+/// stored value. Returns the number of corrupt entries purged.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_verify_all() -> u64 {
+    let cache = get_cache();
+    let mut purged = 0u64;
+
+    for shard in &cache.shards {
+        let mut map = shard.lock().unwrap();
+        let corrupt_keys: Vec<String> = map
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired() && entry.verified_value().is_none())
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in corrupt_keys {
+            if let Some(entry) = map.remove(&key) {
+                entry.release_chunks();
+                purged += 1;
+            }
+        }
+    }
+
+    purged
+}
+
 /// Batch operations for improved performance
 #[no_mangle]
 pub extern "C" fn highper_cache_memory_set_multiple(
@@ -213,31 +489,28 @@ pub extern "C" fn highper_cache_memory_set_multiple(
     count: usize,
 ) -> u64 {
     unsafe {
-        init_memory_cache();
-        
-        let mut cache = MEMORY_CACHE.lock().unwrap();
-        if let Some(ref mut map) = *cache {
-            let mut success_count = 0u64;
-            
-            for i in 0..count {
-                let key_ptr = *keys.add(i);
-                let value_ptr = *values.add(i);
-                let ttl = *ttls.add(i);
-                
-                if let (Ok(key_str), Ok(value_str)) = (
-                    c_str_to_string(key_ptr),
-                    c_str_to_string(value_ptr),
-                ) {
-                    let entry = CacheEntry::new(value_str, ttl);
-                    map.insert(key_str, entry);
-                    success_count += 1;
+        let cache = get_cache();
+        let mut success_count = 0u64;
+
+        for i in 0..count {
+            let key_ptr = *keys.add(i);
+            let value_ptr = *values.add(i);
+            let ttl = *ttls.add(i);
+
+            if let (Ok(key_str), Ok(value_str)) = (c_str_to_string(key_ptr), c_str_to_string(value_ptr)) {
+                let shard = cache.shard_for(&key_str);
+                let mut map = shard.lock().unwrap();
+                cache.evict_if_full(&mut map, &key_str);
+                let entry = CacheEntry::new(value_str, ttl);
+                if let Some(previous) = map.insert(key_str, entry) {
+                    previous.release_chunks();
                 }
+                success_count += 1;
             }
-            
-            return success_count;
         }
+
+        success_count
     }
-    0
 }
 
 #[no_mangle]
@@ -246,34 +519,139 @@ pub extern "C" fn highper_cache_memory_get_multiple(
     count: usize,
 ) -> *mut c_char {
     unsafe {
-        init_memory_cache();
-        
-        let mut cache = MEMORY_CACHE.lock().unwrap();
-        if let Some(ref mut map) = *cache {
-            let mut results = HashMap::new();
-            
-            for i in 0..count {
-                let key_ptr = *keys.add(i);
-                if let Ok(key_str) = c_str_to_string(key_ptr) {
-                    if let Some(entry) = map.get(&key_str) {
-                        if !entry.is_expired() {
-                            results.insert(key_str, entry.value.clone());
-                        } else {
-                            map.remove(&key_str);
+        let cache = get_cache();
+        let mut results = HashMap::new();
+
+        for i in 0..count {
+            let key_ptr = *keys.add(i);
+            if let Ok(key_str) = c_str_to_string(key_ptr) {
+                let shard = cache.shard_for(&key_str);
+                let mut map = shard.lock().unwrap();
+                if let Some(entry) = map.get(&key_str) {
+                    if entry.is_expired() {
+                        if let Some(entry) = map.remove(&key_str) {
+                            entry.release_chunks();
                         }
+                    } else if let Some(value) = entry.verified_value() {
+                        results.insert(key_str, value);
+                    } else if let Some(entry) = map.remove(&key_str) {
+                        entry.release_chunks();
                     }
                 }
             }
-            
-            if let Ok(json) = serde_json::to_string(&results) {
-                return string_to_c_str(json);
-            }
+        }
+
+        if let Ok(json) = serde_json::to_string(&results) {
+            return string_to_c_str(json);
         }
     }
     std::ptr::null_mut()
 }
 
-/// Redis operations (basic implementation - would need connection management)
+/// Stores `value` in deduplicated form: it is split into content-defined
+/// chunks (FastCDC), each unique chunk is stored once in a shared content
+/// store, and the entry keeps only the ordered list of chunk hashes. This
+/// trades a little CPU for shrinking memory use when many stored values are
+/// large and near-identical.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_set_dedup(
+    key: *const c_char,
+    value: *const c_char,
+    ttl: u64,
+) -> bool {
+    unsafe {
+        if let (Ok(key_str), Ok(value_str)) = (c_str_to_string(key), c_str_to_string(value)) {
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let chunk_hashes = dedup::store_value(value_str.as_bytes());
+            let entry = CacheEntry::new_chunked(chunk_hashes, value_str.as_bytes(), ttl);
+
+            let mut map = shard.lock().unwrap();
+            cache.evict_if_full(&mut map, &key_str);
+            if let Some(previous) = map.insert(key_str, entry) {
+                previous.release_chunks();
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Stores `value` compressed at rest when it's at least `min_size` bytes,
+/// tagging the entry with the codec used so `get` transparently
+/// decompresses. `codec` is `0` (none), `1` (LZ4), or `2` (Zstd, using
+/// `zstd_level`). Values below `min_size` are stored uncompressed.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_set_compressed(
+    key: *const c_char,
+    value: *const c_char,
+    ttl: u64,
+    codec: i32,
+    zstd_level: i32,
+    min_size: usize,
+) -> bool {
+    unsafe {
+        if let (Ok(key_str), Ok(value_str)) = (c_str_to_string(key), c_str_to_string(value)) {
+            let cache = get_cache();
+            let shard = cache.shard_for(&key_str);
+
+            let entry = if value_str.len() >= min_size {
+                let codec = codec_from_ffi(codec, zstd_level);
+                match codec.compress(value_str.as_bytes()) {
+                    Some(compressed) => {
+                        CacheEntry::new_compressed(codec, compressed, value_str.as_bytes(), ttl)
+                    }
+                    None => CacheEntry::new(value_str, ttl),
+                }
+            } else {
+                CacheEntry::new(value_str, ttl)
+            };
+
+            let mut map = shard.lock().unwrap();
+            cache.evict_if_full(&mut map, &key_str);
+            if let Some(previous) = map.insert(key_str, entry) {
+                previous.release_chunks();
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Reports the ratio of compressed bytes stored to logical (decompressed)
+/// bytes across every entry stored via `highper_cache_memory_set_compressed`
+/// that is held in compressed-at-rest form (including codec `0`, which keeps
+/// the bytes unchanged). Entries stored as-is because they were below
+/// `min_size` are not counted. `1.0` means no savings; lower is better.
+/// Returns `1.0` if no such entry is currently stored.
+#[no_mangle]
+pub extern "C" fn highper_cache_memory_compression_ratio() -> f64 {
+    let cache = get_cache();
+    let mut bytes_stored = 0u64;
+    let mut bytes_logical = 0u64;
+
+    for shard in &cache.shards {
+        let map = shard.lock().unwrap();
+        for entry in map.values() {
+            if let CacheValue::Compressed { bytes, .. } = &entry.value {
+                if let Some(value) = entry.materialize() {
+                    bytes_stored += bytes.len() as u64;
+                    bytes_logical += value.len() as u64;
+                }
+            }
+        }
+    }
+
+    if bytes_logical == 0 {
+        return 1.0;
+    }
+    bytes_stored as f64 / bytes_logical as f64
+}
+
+/// Redis operations. The data operations below go through the pooled
+/// connection registry in `redis_backend`; `ping` opens a fresh connection
+/// on every call.
 #[no_mangle]
 pub extern "C" fn highper_cache_redis_ping(
     host: *const c_char,
@@ -282,7 +660,7 @@ pub extern "C" fn highper_cache_redis_ping(
     unsafe {
         if let Ok(host_str) = c_str_to_string(host) {
             let connection_string = format!("redis://{}:{}", host_str, port);
-            
+
             if let Ok(client) = redis::Client::open(connection_string) {
                 if let Ok(mut conn) = client.get_connection() {
                     let result: RedisResult<String> = conn.ping();
@@ -294,6 +672,149 @@ pub extern "C" fn highper_cache_redis_ping(
     false
 }
 
+/// Synchronous set: waits for Redis to confirm the write, reconnecting and
+/// retrying once if the pooled connection has dropped.
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_set(
+    host: *const c_char,
+    port: u16,
+    key: *const c_char,
+    value: *const c_char,
+    ttl: u64,
+) -> bool {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str), Ok(value_str)) =
+            (c_str_to_string(host), c_str_to_string(key), c_str_to_string(value))
+        {
+            return redis_backend::set(&host_str, port, &key_str, &value_str, ttl);
+        }
+    }
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_get(host: *const c_char, port: u16, key: *const c_char) -> *mut c_char {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str)) = (c_str_to_string(host), c_str_to_string(key)) {
+            if let Some(value) = redis_backend::get(&host_str, port, &key_str) {
+                return string_to_c_str(value);
+            }
+        }
+    }
+    std::ptr::null_mut()
+}
+
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_delete(host: *const c_char, port: u16, key: *const c_char) -> bool {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str)) = (c_str_to_string(host), c_str_to_string(key)) {
+            return redis_backend::delete(&host_str, port, &key_str);
+        }
+    }
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_exists(host: *const c_char, port: u16, key: *const c_char) -> bool {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str)) = (c_str_to_string(host), c_str_to_string(key)) {
+            return redis_backend::exists(&host_str, port, &key_str);
+        }
+    }
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_set_multiple(
+    host: *const c_char,
+    port: u16,
+    keys: *const *const c_char,
+    values: *const *const c_char,
+    ttls: *const u64,
+    count: usize,
+) -> u64 {
+    unsafe {
+        if let Ok(host_str) = c_str_to_string(host) {
+            let mut items = Vec::with_capacity(count);
+            for i in 0..count {
+                let key_ptr = *keys.add(i);
+                let value_ptr = *values.add(i);
+                let ttl = *ttls.add(i);
+                if let (Ok(key_str), Ok(value_str)) = (c_str_to_string(key_ptr), c_str_to_string(value_ptr)) {
+                    items.push((key_str, value_str, ttl));
+                }
+            }
+            return redis_backend::set_multiple(&host_str, port, &items);
+        }
+    }
+    0
+}
+
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_get_multiple(
+    host: *const c_char,
+    port: u16,
+    keys: *const *const c_char,
+    count: usize,
+) -> *mut c_char {
+    unsafe {
+        if let Ok(host_str) = c_str_to_string(host) {
+            let mut key_strs = Vec::with_capacity(count);
+            for i in 0..count {
+                if let Ok(key_str) = c_str_to_string(*keys.add(i)) {
+                    key_strs.push(key_str);
+                }
+            }
+            let results = redis_backend::get_multiple(&host_str, port, &key_strs);
+            if let Ok(json) = serde_json::to_string(&results) {
+                return string_to_c_str(json);
+            }
+        }
+    }
+    std::ptr::null_mut()
+}
+
+/// Fire-and-forget write: enqueues the write on a background thread and
+/// returns immediately, for write-heavy workloads that don't need to wait
+/// on the round trip to Redis.
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_set_async(
+    host: *const c_char,
+    port: u16,
+    key: *const c_char,
+    value: *const c_char,
+    ttl: u64,
+) -> bool {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str), Ok(value_str)) =
+            (c_str_to_string(host), c_str_to_string(key), c_str_to_string(value))
+        {
+            return redis_backend::set_async(&host_str, port, &key_str, &value_str, ttl);
+        }
+    }
+    false
+}
+
+/// Tiered read-through: checks the sharded memory cache first and falls
+/// back to Redis on a miss, promoting the value back into memory with
+/// `promote_ttl` for subsequent reads.
+#[no_mangle]
+pub extern "C" fn highper_cache_redis_get_through(
+    host: *const c_char,
+    port: u16,
+    key: *const c_char,
+    promote_ttl: u64,
+) -> *mut c_char {
+    unsafe {
+        if let (Ok(host_str), Ok(key_str)) = (c_str_to_string(host), c_str_to_string(key)) {
+            if let Some(value) = redis_backend::get_through(&host_str, port, &key_str, promote_ttl) {
+                return string_to_c_str(value);
+            }
+        }
+    }
+    std::ptr::null_mut()
+}
+
 /// Compression utilities
 #[no_mangle]
 pub extern "C" fn highper_cache_compress_lz4(
@@ -474,4 +995,171 @@ mod tests {
             highper_cache_free_string(decompressed);
         }
     }
+
+    #[test]
+    fn test_memory_cache_dedup_roundtrip() {
+        let key = CString::new("dedup_test").unwrap();
+        // Varied, non-repeating content so this actually splits into more
+        // than one content-defined chunk; a constant-byte fixture such as
+        // `"z"` never trips the Gear fingerprint's boundary check.
+        let payload: String = (0..50_000u32)
+            .map(|i| (b'a' + ((i.wrapping_mul(2_654_435_761) >> 8) % 26) as u8) as char)
+            .collect();
+        let value = CString::new(payload.clone()).unwrap();
+
+        assert!(highper_cache_memory_set_dedup(key.as_ptr(), value.as_ptr(), 3600));
+        let result = highper_cache_memory_get(key.as_ptr());
+        assert!(!result.is_null());
+
+        unsafe {
+            let result_str = CStr::from_ptr(result).to_str().unwrap();
+            assert_eq!(result_str, payload);
+            highper_cache_free_string(result);
+        }
+
+        assert!(highper_cache_memory_delete(key.as_ptr()));
+    }
+
+    #[test]
+    fn test_memory_cache_verify() {
+        let key = CString::new("verify_test").unwrap();
+        let value = CString::new("verify_value").unwrap();
+
+        assert!(highper_cache_memory_set(key.as_ptr(), value.as_ptr(), 3600));
+        assert_eq!(highper_cache_memory_verify(key.as_ptr()), 1);
+
+        let missing_key = CString::new("verify_missing").unwrap();
+        assert_eq!(highper_cache_memory_verify(missing_key.as_ptr()), 0);
+
+        assert!(highper_cache_memory_delete(key.as_ptr()));
+    }
+
+    #[test]
+    fn test_memory_cache_verify_detects_corruption() {
+        let key_str = "verify_corrupt_test".to_string();
+
+        // Manufacture an entry with a checksum that deliberately doesn't
+        // match its value, standing in for on-disk/in-memory corruption.
+        let cache = get_cache();
+        let shard = cache.shard_for(&key_str);
+        {
+            let mut map = shard.lock().unwrap();
+            map.insert(
+                key_str.clone(),
+                CacheEntry {
+                    value: CacheValue::Raw("original".to_string()),
+                    checksum: xxh3::hash64(b"original") ^ 1,
+                    expires_at: None,
+                    created_at: 0,
+                },
+            );
+        }
+
+        let key = CString::new(key_str.as_str()).unwrap();
+        assert_eq!(highper_cache_memory_verify(key.as_ptr()), -1);
+        // The corrupt entry was purged as a side effect of the verify above.
+        assert_eq!(highper_cache_memory_verify(key.as_ptr()), 0);
+    }
+
+    /// Compressible but non-constant text, so a codec that preserves length
+    /// while corrupting bytes (or just repeats the first byte) would still
+    /// fail the content-equality assertion below.
+    fn compressible_text(repeats: usize) -> String {
+        "The quick brown fox jumps over the lazy dog. ".repeat(repeats)
+    }
+
+    #[test]
+    fn test_memory_cache_set_compressed_roundtrip_lz4() {
+        let key = CString::new("compressed_lz4_test").unwrap();
+        let payload = compressible_text(200);
+        let value = CString::new(payload.clone()).unwrap();
+
+        // codec = 1 (LZ4), min_size = 0 so it always compresses.
+        assert!(highper_cache_memory_set_compressed(key.as_ptr(), value.as_ptr(), 3600, 1, 0, 0));
+        let result = highper_cache_memory_get(key.as_ptr());
+        assert!(!result.is_null());
+
+        unsafe {
+            let result_str = CStr::from_ptr(result).to_str().unwrap();
+            assert_eq!(result_str, payload);
+            highper_cache_free_string(result);
+        }
+
+        assert!(highper_cache_memory_compression_ratio() < 1.0);
+        assert!(highper_cache_memory_delete(key.as_ptr()));
+    }
+
+    #[test]
+    fn test_memory_cache_set_compressed_roundtrip_zstd() {
+        let key = CString::new("compressed_zstd_test").unwrap();
+        let payload = compressible_text(300);
+        let value = CString::new(payload.clone()).unwrap();
+
+        // codec = 2 (Zstd) at level 3, min_size = 0 so it always compresses.
+        assert!(highper_cache_memory_set_compressed(key.as_ptr(), value.as_ptr(), 3600, 2, 3, 0));
+        let result = highper_cache_memory_get(key.as_ptr());
+        assert!(!result.is_null());
+
+        unsafe {
+            let result_str = CStr::from_ptr(result).to_str().unwrap();
+            assert_eq!(result_str, payload);
+            highper_cache_free_string(result);
+        }
+
+        assert!(highper_cache_memory_compression_ratio() < 1.0);
+        assert!(highper_cache_memory_delete(key.as_ptr()));
+    }
+
+    #[test]
+    fn test_memory_cache_set_compressed_below_min_size_stays_uncompressed() {
+        let key = CString::new("compressed_below_min_size_test").unwrap();
+        let value = CString::new("short").unwrap();
+
+        // min_size is larger than the value, so it's stored as-is.
+        assert!(highper_cache_memory_set_compressed(key.as_ptr(), value.as_ptr(), 3600, 1, 0, 1024));
+
+        let cache = get_cache();
+        let shard = cache.shard_for("compressed_below_min_size_test");
+        {
+            let map = shard.lock().unwrap();
+            assert!(matches!(
+                map.get("compressed_below_min_size_test").unwrap().value,
+                CacheValue::Raw(_)
+            ));
+        }
+
+        let result = highper_cache_memory_get(key.as_ptr());
+        assert!(!result.is_null());
+        unsafe {
+            let result_str = CStr::from_ptr(result).to_str().unwrap();
+            assert_eq!(result_str, "short");
+            highper_cache_free_string(result);
+        }
+
+        assert!(highper_cache_memory_delete(key.as_ptr()));
+    }
+
+    #[test]
+    fn test_memory_configure_releases_dedup_chunks_from_old_cache() {
+        let key_str = "configure_dedup_test".to_string();
+        let value_str = "z".repeat(50_000);
+
+        let cache = get_cache();
+        let shard = cache.shard_for(&key_str);
+        let chunk_hashes = dedup::store_value(value_str.as_bytes());
+        {
+            let mut map = shard.lock().unwrap();
+            map.insert(
+                key_str.clone(),
+                CacheEntry::new_chunked(chunk_hashes.clone(), value_str.as_bytes(), 3600),
+            );
+        }
+        assert_eq!(dedup::reassemble(&chunk_hashes).as_deref(), Some(value_str.as_bytes()));
+
+        // The entry above held the only reference to these chunks once
+        // `configure` drops the old `ShardedCache`, so they must be released
+        // rather than leaked in the content store.
+        assert!(highper_cache_memory_configure(8, 0));
+        assert_eq!(dedup::reassemble(&chunk_hashes), None);
+    }
 }
\ No newline at end of file
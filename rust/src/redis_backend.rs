@@ -0,0 +1,248 @@
+//! Redis-backed cache operations on top of a pooled, reusable connection
+//! registry, plus a tiered read-through mode that checks the sharded memory
+//! cache before falling back to Redis.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use redis::{Client, Commands, RedisResult};
+
+use crate::{get_cache, CacheEntry};
+
+/// A pooled Redis connection, lazily established and reconnected on demand.
+struct PooledConnection {
+    client: Client,
+    connection: Mutex<Option<redis::Connection>>,
+}
+
+impl PooledConnection {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            connection: Mutex::new(None),
+        }
+    }
+
+    /// Runs `f` against a live connection, reconnecting once and retrying if
+    /// the pooled connection has dropped.
+    fn with_connection<T>(&self, mut f: impl FnMut(&mut redis::Connection) -> RedisResult<T>) -> RedisResult<T> {
+        let mut guard = self.connection.lock().unwrap();
+        if guard.is_none() {
+            *guard = Some(self.client.get_connection()?);
+        }
+
+        match f(guard.as_mut().unwrap()) {
+            Ok(value) => Ok(value),
+            Err(_) => {
+                *guard = Some(self.client.get_connection()?);
+                f(guard.as_mut().unwrap())
+            }
+        }
+    }
+}
+
+/// Registry of pooled connections, keyed by connection string, so repeated
+/// calls against the same Redis instance reuse a connection instead of
+/// reconnecting every time.
+static POOL: Mutex<Option<HashMap<String, Arc<PooledConnection>>>> = Mutex::new(None);
+
+fn connection_string(host: &str, port: u16) -> String {
+    format!("redis://{}:{}", host, port)
+}
+
+fn pooled(host: &str, port: u16) -> Option<Arc<PooledConnection>> {
+    let key = connection_string(host, port);
+
+    let mut guard = POOL.lock().unwrap();
+    let registry = guard.get_or_insert_with(HashMap::new);
+    if let Some(existing) = registry.get(&key) {
+        return Some(existing.clone());
+    }
+
+    let client = Client::open(key.clone()).ok()?;
+    let entry = Arc::new(PooledConnection::new(client));
+    registry.insert(key, entry.clone());
+    Some(entry)
+}
+
+/// Synchronous set: waits for Redis to confirm the write.
+pub(crate) fn set(host: &str, port: u16, key: &str, value: &str, ttl: u64) -> bool {
+    let Some(pool) = pooled(host, port) else {
+        return false;
+    };
+    let result: RedisResult<()> = pool.with_connection(|conn| {
+        if ttl > 0 {
+            conn.set_ex(key, value, ttl)
+        } else {
+            conn.set(key, value)
+        }
+    });
+    result.is_ok()
+}
+
+pub(crate) fn get(host: &str, port: u16, key: &str) -> Option<String> {
+    let pool = pooled(host, port)?;
+    let result: RedisResult<Option<String>> = pool.with_connection(|conn| conn.get(key));
+    result.ok().flatten()
+}
+
+pub(crate) fn delete(host: &str, port: u16, key: &str) -> bool {
+    let Some(pool) = pooled(host, port) else {
+        return false;
+    };
+    let result: RedisResult<i64> = pool.with_connection(|conn| conn.del(key));
+    matches!(result, Ok(count) if count > 0)
+}
+
+pub(crate) fn exists(host: &str, port: u16, key: &str) -> bool {
+    let Some(pool) = pooled(host, port) else {
+        return false;
+    };
+    let result: RedisResult<bool> = pool.with_connection(|conn| conn.exists(key));
+    result.unwrap_or(false)
+}
+
+pub(crate) fn set_multiple(host: &str, port: u16, items: &[(String, String, u64)]) -> u64 {
+    let Some(pool) = pooled(host, port) else {
+        return 0;
+    };
+
+    let mut success_count = 0u64;
+    for (key, value, ttl) in items {
+        let result: RedisResult<()> = pool.with_connection(|conn| {
+            if *ttl > 0 {
+                conn.set_ex(key, value, *ttl)
+            } else {
+                conn.set(key, value)
+            }
+        });
+        if result.is_ok() {
+            success_count += 1;
+        }
+    }
+    success_count
+}
+
+pub(crate) fn get_multiple(host: &str, port: u16, keys: &[String]) -> HashMap<String, String> {
+    let mut results = HashMap::new();
+    let Some(pool) = pooled(host, port) else {
+        return results;
+    };
+
+    for key in keys {
+        let result: RedisResult<Option<String>> = pool.with_connection(|conn| conn.get(key));
+        if let Ok(Some(value)) = result {
+            results.insert(key.clone(), value);
+        }
+    }
+    results
+}
+
+/// A queued fire-and-forget write, processed on a dedicated background
+/// thread so callers don't block on the round trip to Redis.
+struct WriteJob {
+    host: String,
+    port: u16,
+    key: String,
+    value: String,
+    ttl: u64,
+}
+
+static WRITE_QUEUE: OnceLock<Sender<WriteJob>> = OnceLock::new();
+
+fn write_queue() -> &'static Sender<WriteJob> {
+    WRITE_QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<WriteJob>();
+        thread::spawn(move || {
+            for job in receiver {
+                set(&job.host, job.port, &job.key, &job.value, job.ttl);
+            }
+        });
+        sender
+    })
+}
+
+/// Enqueues a write and returns immediately without waiting for Redis to
+/// confirm it, for write-heavy workloads that can tolerate a fire-and-forget
+/// write path.
+pub(crate) fn set_async(host: &str, port: u16, key: &str, value: &str, ttl: u64) -> bool {
+    write_queue()
+        .send(WriteJob {
+            host: host.to_string(),
+            port,
+            key: key.to_string(),
+            value: value.to_string(),
+            ttl,
+        })
+        .is_ok()
+}
+
+/// Returns the key's remaining TTL in whole seconds (rounded up) by asking
+/// Redis for `PTTL`, or `None` if Redis reports no expiry (`-1`) or the key
+/// is missing (`-2`).
+fn remaining_ttl_secs(host: &str, port: u16, key: &str) -> Option<u64> {
+    let pool = pooled(host, port)?;
+    let result: RedisResult<i64> = pool.with_connection(|conn| conn.pttl(key));
+    match result {
+        Ok(millis) if millis > 0 => Some((millis as u64 + 999) / 1000),
+        _ => None,
+    }
+}
+
+/// Tiered read: checks the sharded memory cache first, falling back to
+/// Redis on a miss and promoting the value back into memory. The promoted
+/// entry's TTL is seeded from Redis's own `PTTL` for the key so the two
+/// tiers don't drift apart; `promote_ttl` is only a fallback for when Redis
+/// reports no TTL or the key has already expired there.
+pub(crate) fn get_through(host: &str, port: u16, key: &str, promote_ttl: u64) -> Option<String> {
+    let cache = get_cache();
+    let shard = cache.shard_for(key);
+
+    {
+        let mut map = shard.lock().unwrap();
+        if let Some(entry) = map.get(key) {
+            if entry.is_expired() {
+                if let Some(entry) = map.remove(key) {
+                    entry.release_chunks();
+                }
+            } else if let Some(value) = entry.verified_value() {
+                return Some(value);
+            } else if let Some(entry) = map.remove(key) {
+                entry.release_chunks();
+            }
+        }
+    }
+
+    let value = get(host, port, key)?;
+    let ttl = remaining_ttl_secs(host, port, key).unwrap_or(promote_ttl);
+
+    let mut map = shard.lock().unwrap();
+    cache.evict_if_full(&mut map, key);
+    if let Some(previous) = map.insert(key.to_string(), CacheEntry::new(value.clone(), ttl)) {
+        previous.release_chunks();
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_through_returns_cached_value_without_contacting_redis() {
+        let key = "get_through_memory_hit";
+        let cache = get_cache();
+        let shard = cache.shard_for(key);
+        {
+            let mut map = shard.lock().unwrap();
+            map.insert(key.to_string(), CacheEntry::new("cached-value".to_string(), 3600));
+        }
+
+        // Port 0 never accepts connections, so this only passes if the
+        // memory-cache hit short-circuited before any Redis call was made.
+        let result = get_through("127.0.0.1", 0, key, 60);
+        assert_eq!(result.as_deref(), Some("cached-value"));
+    }
+}